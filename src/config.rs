@@ -0,0 +1,155 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+/// A single key chord a keymap entry can bind. Modifiers aren't modeled yet
+/// since none of the current actions need them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum KeyChord {
+    Char(char),
+    Esc,
+    Enter,
+}
+
+impl KeyChord {
+    fn matches(self, event: KeyEvent) -> bool {
+        matches!(
+            (self, event.code),
+            (KeyChord::Char(bound), KeyCode::Char(pressed)) if bound == pressed
+        ) || matches!((self, event.code), (KeyChord::Esc, KeyCode::Esc))
+            || matches!((self, event.code), (KeyChord::Enter, KeyCode::Enter))
+    }
+}
+
+/// Named actions a key chord can be bound to, dispatched from `run_app`
+/// instead of matching on `KeyCode` directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Action {
+    Quit,
+    Refresh,
+    ToggleView,
+    SpeedUp,
+    SlowDown,
+}
+
+/// Which `LocationSource` to prefer at startup. `Auto` keeps the existing
+/// portal-with-IP-fallback behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum LocationPreference {
+    Auto,
+    Ip,
+    Portal,
+}
+
+impl Default for LocationPreference {
+    fn default() -> LocationPreference {
+        LocationPreference::Auto
+    }
+}
+
+/// Color names `ron` can deserialize, mapped onto `ratatui::style::Color`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum MarkerColor {
+    White,
+    Yellow,
+    Red,
+    Green,
+    Cyan,
+    Magenta,
+}
+
+impl MarkerColor {
+    pub fn to_color(self) -> Color {
+        match self {
+            MarkerColor::White => Color::White,
+            MarkerColor::Yellow => Color::Yellow,
+            MarkerColor::Red => Color::Red,
+            MarkerColor::Green => Color::Green,
+            MarkerColor::Cyan => Color::Cyan,
+            MarkerColor::Magenta => Color::Magenta,
+        }
+    }
+}
+
+impl Default for MarkerColor {
+    fn default() -> MarkerColor {
+        MarkerColor::Yellow
+    }
+}
+
+/// Settings and keybindings loaded from `~/.config/cli-locate/config.ron`.
+/// Missing or unparseable config files fall back to `Config::default()`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub keymap: HashMap<KeyChord, Action>,
+    pub tick_rate_ms: u64,
+    pub refresh_interval_secs: u64,
+    pub location_source: LocationPreference,
+    pub marker_color: MarkerColor,
+    pub notifications_enabled: bool,
+    pub notify_distance_threshold_km: f64,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            keymap: default_keymap(),
+            tick_rate_ms: 250,
+            refresh_interval_secs: 300,
+            location_source: LocationPreference::default(),
+            marker_color: MarkerColor::default(),
+            notifications_enabled: false,
+            notify_distance_threshold_km: 5.0,
+        }
+    }
+}
+
+impl Config {
+    pub fn tick_rate(&self) -> Duration {
+        Duration::from_millis(self.tick_rate_ms)
+    }
+
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.refresh_interval_secs)
+    }
+
+    pub fn action_for(&self, event: KeyEvent) -> Option<Action> {
+        self.keymap
+            .iter()
+            .find(|(chord, _)| chord.matches(event))
+            .map(|(_, action)| *action)
+    }
+}
+
+fn default_keymap() -> HashMap<KeyChord, Action> {
+    HashMap::from([
+        (KeyChord::Char('q'), Action::Quit),
+        (KeyChord::Esc, Action::Quit),
+        (KeyChord::Char('r'), Action::Refresh),
+        (KeyChord::Char('v'), Action::ToggleView),
+        (KeyChord::Char(']'), Action::SpeedUp),
+        (KeyChord::Char('['), Action::SlowDown),
+    ])
+}
+
+fn config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("cli-locate")
+        .join("config.ron")
+}
+
+/// Loads `Config` from disk, falling back to `Config::default()` when the
+/// file is missing or fails to parse. A present-but-invalid file logs its
+/// parse error to stderr so a broken config doesn't fail silently.
+pub fn load() -> Config {
+    match std::fs::read_to_string(config_path()) {
+        Ok(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+            eprintln!("cli-locate: failed to parse {:?}: {err}", config_path());
+            Config::default()
+        }),
+        Err(_) => Config::default(),
+    }
+}
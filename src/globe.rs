@@ -0,0 +1,69 @@
+/// Which projection the map canvas currently renders in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewMode {
+    Flat,
+    Globe,
+}
+
+impl ViewMode {
+    pub fn toggled(self) -> ViewMode {
+        match self {
+            ViewMode::Flat => ViewMode::Globe,
+            ViewMode::Globe => ViewMode::Flat,
+        }
+    }
+}
+
+/// Orthographic projection of a (lon, lat) point on the unit sphere, rotated
+/// by `rotation` degrees of longitude and scaled to `radius` screen units.
+///
+/// Returns `None` when the point falls on the back hemisphere (`z < 0`), so
+/// callers can skip drawing it.
+pub fn project(lon: f64, lat: f64, rotation: f64, radius: f64) -> Option<(f64, f64, f64)> {
+    let lambda = (lon + rotation).to_radians();
+    let phi = lat.to_radians();
+
+    let z = phi.cos() * lambda.cos();
+    if z < 0.0 {
+        return None;
+    }
+
+    let x = phi.cos() * lambda.sin() * radius;
+    let y = phi.sin() * radius;
+    Some((x, y, z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} != {b}");
+    }
+
+    #[test]
+    fn back_hemisphere_point_is_culled() {
+        assert_eq!(project(180.0, 0.0, 0.0, 1.0), None);
+    }
+
+    #[test]
+    fn front_hemisphere_point_has_known_coordinates() {
+        let (x, y, z) = project(0.0, 0.0, 0.0, 1.0).expect("front hemisphere");
+        assert_close(x, 0.0);
+        assert_close(y, 0.0);
+        assert_close(z, 1.0);
+    }
+
+    #[test]
+    fn rotation_shifts_longitude_before_projecting() {
+        let (x, y, z) = project(45.0, 0.0, 0.0, 1.0).expect("front hemisphere");
+        assert_close(x, std::f64::consts::FRAC_1_SQRT_2);
+        assert_close(y, 0.0);
+        assert_close(z, std::f64::consts::FRAC_1_SQRT_2);
+
+        let rotated = project(0.0, 0.0, 45.0, 1.0).expect("front hemisphere");
+        assert_close(rotated.0, x);
+        assert_close(rotated.1, y);
+        assert_close(rotated.2, z);
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct LocationResponse {
+    lat: f64,
+    lon: f64,
+    city: Option<String>,
+    country: Option<String>,
+}
+
+/// A single location fix, enriched with whatever extra fields the source
+/// was able to provide. Fields that only the portal can supply (altitude,
+/// speed, heading, accuracy) are `None` when the fix came from `IpApiSource`.
+#[derive(Debug, Clone)]
+pub struct Location {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub city: String,
+    pub country: String,
+    pub altitude: Option<f64>,
+    pub speed: Option<f64>,
+    pub heading: Option<f64>,
+    pub accuracy: Option<Accuracy>,
+    pub timestamp: Option<u64>,
+}
+
+impl Location {
+    fn from_ip(response: LocationResponse) -> Location {
+        Location {
+            latitude: response.lat,
+            longitude: response.lon,
+            city: response.city.unwrap_or_else(|| "Unknown".to_string()),
+            country: response.country.unwrap_or_else(|| "Unknown".to_string()),
+            altitude: None,
+            speed: None,
+            heading: None,
+            accuracy: None,
+            timestamp: None,
+        }
+    }
+}
+
+/// Requested precision for a portal location session, mirroring
+/// `ashpd::desktop::location::Accuracy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Accuracy {
+    None,
+    Country,
+    City,
+    Neighborhood,
+    Street,
+    Exact,
+}
+
+/// A backend capable of producing a `Location` fix. Implementors may be
+/// network-based (`IpApiSource`) or backed by the host's own location
+/// service (`PortalSource`).
+#[async_trait]
+pub trait LocationSource: Send {
+    async fn fetch(&mut self) -> Result<Location>;
+
+    /// Short label shown in the info bar, e.g. "ip-api" or "portal".
+    fn name(&self) -> &'static str;
+}
+
+/// Coarse, network-based location via `http://ip-api.com/json/`. Requires no
+/// permissions and works anywhere `reqwest` can reach the internet, but the
+/// fix is only as accurate as the IP's geolocation database entry.
+pub struct IpApiSource;
+
+#[async_trait]
+impl LocationSource for IpApiSource {
+    async fn fetch(&mut self) -> Result<Location> {
+        let response = reqwest::get("http://ip-api.com/json/")
+            .await?
+            .json::<LocationResponse>()
+            .await?;
+
+        Ok(Location::from_ip(response))
+    }
+
+    fn name(&self) -> &'static str {
+        "ip-api"
+    }
+}
+
+/// Real device positioning via the freedesktop location portal
+/// (`org.freedesktop.portal.Location`), accessed through `ashpd`.
+///
+/// Each fetch opens a fresh location session at the requested `Accuracy`,
+/// starts it, waits for the first `LocationUpdated` signal, then closes the
+/// session. City/country are set to "Unknown" since the portal only reports
+/// coordinates; callers that want reverse-geocoded names should overlay an
+/// `IpApiSource` fetch.
+pub struct PortalSource {
+    pub accuracy: Accuracy,
+}
+
+impl PortalSource {
+    pub fn new(accuracy: Accuracy) -> PortalSource {
+        PortalSource { accuracy }
+    }
+
+    fn to_portal_accuracy(self_accuracy: Accuracy) -> ashpd::desktop::location::Accuracy {
+        match self_accuracy {
+            Accuracy::None => ashpd::desktop::location::Accuracy::None,
+            Accuracy::Country => ashpd::desktop::location::Accuracy::Country,
+            Accuracy::City => ashpd::desktop::location::Accuracy::City,
+            Accuracy::Neighborhood => ashpd::desktop::location::Accuracy::Neighborhood,
+            Accuracy::Street => ashpd::desktop::location::Accuracy::Street,
+            Accuracy::Exact => ashpd::desktop::location::Accuracy::Exact,
+        }
+    }
+}
+
+#[async_trait]
+impl LocationSource for PortalSource {
+    async fn fetch(&mut self) -> Result<Location> {
+        use ashpd::desktop::location::LocationProxy;
+        use futures_util::StreamExt;
+
+        let proxy = LocationProxy::new().await?;
+        let session = proxy
+            .create_session(None, None, Some(Self::to_portal_accuracy(self.accuracy)))
+            .await?;
+
+        let mut updates = proxy.receive_location_updated().await?;
+        proxy.start(&session, None).await?;
+
+        let update = updates
+            .next()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("portal closed without a location update"))?;
+
+        session.close().await?;
+
+        Ok(Location {
+            latitude: update.latitude(),
+            longitude: update.longitude(),
+            city: "Unknown".to_string(),
+            country: "Unknown".to_string(),
+            altitude: update.altitude(),
+            speed: update.speed(),
+            heading: update.heading(),
+            accuracy: Some(self.accuracy),
+            timestamp: Some(update.timestamp()),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "portal"
+    }
+}
+
+/// Picks `PortalSource` when the freedesktop location portal is reachable,
+/// falling back to `IpApiSource` otherwise. Called once at startup; `App`
+/// keeps whichever source this returns for the remainder of the session.
+///
+/// The probe fetch used to decide reachability is itself a real fix, so it
+/// is returned alongside the source instead of being discarded and
+/// re-fetched immediately after (which would cost a second portal round
+/// trip, and potentially a second permission prompt, for no reason).
+pub async fn pick_source(accuracy: Accuracy) -> (Box<dyn LocationSource>, Option<Location>) {
+    let mut portal = PortalSource::new(accuracy);
+    match portal.fetch().await {
+        Ok(location) => (Box::new(portal), Some(location)),
+        Err(_) => (Box::new(IpApiSource), None),
+    }
+}
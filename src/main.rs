@@ -1,76 +1,102 @@
+mod config;
+mod globe;
+mod location;
+mod notify;
+mod terminal;
+
 use anyhow::Result;
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use config::{Action, Config, LocationPreference};
+use crossterm::event::{self, Event};
+use globe::ViewMode;
+use location::{pick_source, Accuracy, IpApiSource, Location, LocationSource, PortalSource};
 use ratatui::{
-    backend::{Backend, CrosstermBackend},
+    backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Style},
     symbols,
     widgets::{Block, Borders, Paragraph, canvas::Canvas},
     Frame, Terminal,
 };
-use reqwest;
-use serde::Deserialize;
-use std::{
-    io,
-    time::{Duration, Instant},
-};
-
-
-#[derive(Debug, Deserialize)]
-struct LocationResponse {
-    lat: f64,
-    lon: f64,
-    city: Option<String>,
-    country: Option<String>,
-}
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone)]
-struct Location {
-    latitude: f64,
-    longitude: f64,
-    city: String,
-    country: String,
-}
+const MIN_ROTATION_SPEED: f64 = 1.0;
+const MAX_ROTATION_SPEED: f64 = 90.0;
 
 struct App {
     location: Option<Location>,
+    last_notified: Option<Location>,
+    source: Box<dyn LocationSource>,
+    config: Config,
     last_update: Instant,
     rotation: f64,
+    rotation_speed: f64,
+    view_mode: ViewMode,
 }
 
 impl App {
-    fn new() -> App {
+    fn new(source: Box<dyn LocationSource>, config: Config, initial_location: Option<Location>) -> App {
         App {
-            location: None,
+            location: initial_location,
+            last_notified: None,
+            source,
+            config,
             last_update: Instant::now(),
             rotation: 0.0,
+            rotation_speed: 10.0,
+            view_mode: ViewMode::Flat,
         }
     }
 
     fn update(&mut self) {
         let now = Instant::now();
         let elapsed = now.duration_since(self.last_update).as_secs_f64();
-        self.rotation += elapsed * 10.0;
+        self.rotation += elapsed * self.rotation_speed;
         self.last_update = now;
     }
-}
 
-async fn get_location() -> Result<Location> {
-    let response = reqwest::get("http://ip-api.com/json/")
-        .await?
-        .json::<LocationResponse>()
-        .await?;
-    
-    Ok(Location {
-        latitude: response.lat,
-        longitude: response.lon,
-        city: response.city.unwrap_or_else(|| "Unknown".to_string()),
-        country: response.country.unwrap_or_else(|| "Unknown".to_string()),
-    })
+    async fn refresh_location(&mut self) {
+        if let Ok(location) = self.source.fetch().await {
+            self.maybe_notify(&location);
+            self.location = Some(location);
+        }
+    }
+
+    fn maybe_notify(&mut self, location: &Location) {
+        if !self.config.notifications_enabled {
+            return;
+        }
+
+        // `None` means this is the first fix of the session: there's nothing
+        // to compare against yet, so seed `last_notified` silently instead of
+        // treating "no prior fix" as a meaningful change.
+        let previous = match self.last_notified.clone() {
+            Some(previous) => previous,
+            None => {
+                self.last_notified = Some(location.clone());
+                return;
+            }
+        };
+
+        // The baseline only advances when a notification actually fires, so
+        // a sequence of small moves that each fall under the threshold still
+        // accumulates into a notification once the total drift exceeds it.
+        if notify::changed_meaningfully(&previous, location, self.config.notify_distance_threshold_km) {
+            notify::notify_location_change(location);
+            self.last_notified = Some(location.clone());
+        }
+    }
+
+    fn toggle_view(&mut self) {
+        self.view_mode = self.view_mode.toggled();
+    }
+
+    fn faster(&mut self) {
+        self.rotation_speed = (self.rotation_speed * 1.5).min(MAX_ROTATION_SPEED);
+    }
+
+    fn slower(&mut self) {
+        self.rotation_speed = (self.rotation_speed / 1.5).max(MIN_ROTATION_SPEED);
+    }
 }
 
 fn ui(f: &mut Frame, app: &App) {
@@ -81,10 +107,21 @@ fn ui(f: &mut Frame, app: &App) {
         .split(f.size());
 
     let info_text = if let Some(ref location) = app.location {
-        format!(
-            "Location: {}, {} | Lat: {:.4}°, Lon: {:.4}°",
-            location.city, location.country, location.latitude, location.longitude
-        )
+        let mut text = format!(
+            "Location: {}, {} | Lat: {:.4}°, Lon: {:.4}° ({})",
+            location.city,
+            location.country,
+            location.latitude,
+            location.longitude,
+            app.source.name()
+        );
+        if let Some(altitude) = location.altitude {
+            text.push_str(&format!(" | Alt: {:.1}m", altitude));
+        }
+        if let Some(accuracy) = location.accuracy {
+            text.push_str(&format!(" | Accuracy: {:?}", accuracy));
+        }
+        text
     } else {
         "Fetching location...".to_string()
     };
@@ -95,41 +132,93 @@ fn ui(f: &mut Frame, app: &App) {
         .alignment(Alignment::Center);
     f.render_widget(info, chunks[0]);
 
-    let canvas = Canvas::default()
-        .block(Block::default().borders(Borders::ALL).title("Globe"))
-        .paint(|ctx| {
-            ctx.draw(&ratatui::widgets::canvas::Map {
-                color: Color::White,
-                resolution: ratatui::widgets::canvas::MapResolution::High,
-            });
-
-            if let Some(ref location) = app.location {
-                let x = location.longitude;
-                let y = location.latitude;
-                
-                ctx.print(x, y, "●");
-                
-                ctx.print(x + 5.0, y + 5.0, format!("{}", location.city));
-            }
+    let title = match app.view_mode {
+        ViewMode::Flat => "Globe (flat) — [v] switch to globe",
+        ViewMode::Globe => "Globe (spinning) — [v] switch to flat",
+    };
+
+    let canvas = match app.view_mode {
+        ViewMode::Flat => Canvas::default()
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .paint(|ctx| {
+                ctx.draw(&ratatui::widgets::canvas::Map {
+                    color: Color::White,
+                    resolution: ratatui::widgets::canvas::MapResolution::High,
+                });
+
+                if let Some(ref location) = app.location {
+                    let x = location.longitude;
+                    let y = location.latitude;
+
+                    ctx.print(
+                        x,
+                        y,
+                        ratatui::text::Span::styled(
+                            "●",
+                            Style::default().fg(app.config.marker_color.to_color()),
+                        ),
+                    );
 
-            for lat in (-90..=90).step_by(30) {
-                for lon in (-180..=180).step_by(30) {
-                    ctx.print(lon as f64, lat as f64, "·");
+                    ctx.print(x + 5.0, y + 5.0, format!("{}", location.city));
                 }
-            }
-        })
-        .marker(symbols::Marker::Braille)
-        .x_bounds([-180.0, 180.0])
-        .y_bounds([-90.0, 90.0]);
+
+                for lat in (-90..=90).step_by(30) {
+                    for lon in (-180..=180).step_by(30) {
+                        ctx.print(lon as f64, lat as f64, "·");
+                    }
+                }
+            })
+            .marker(symbols::Marker::Braille)
+            .x_bounds([-180.0, 180.0])
+            .y_bounds([-90.0, 90.0]),
+        ViewMode::Globe => Canvas::default()
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .paint(|ctx| {
+                const RADIUS: f64 = 90.0;
+
+                for lat in (-90..=90).step_by(15) {
+                    for lon in (-180..=180).step_by(15) {
+                        if let Some((x, y, _)) =
+                            globe::project(lon as f64, lat as f64, app.rotation, RADIUS)
+                        {
+                            ctx.print(x, y, "·");
+                        }
+                    }
+                }
+
+                if let Some(ref location) = app.location {
+                    if let Some((x, y, z)) = globe::project(
+                        location.longitude,
+                        location.latitude,
+                        app.rotation,
+                        RADIUS,
+                    ) {
+                        let marker = if z > 0.85 { "◉" } else { "●" };
+                        let color = if z > 0.85 {
+                            Color::LightYellow
+                        } else {
+                            app.config.marker_color.to_color()
+                        };
+                        ctx.print(x, y, ratatui::text::Span::styled(marker, Style::default().fg(color)));
+                    }
+                }
+            })
+            .marker(symbols::Marker::Braille)
+            .x_bounds([-100.0, 100.0])
+            .y_bounds([-100.0, 100.0]),
+    };
     f.render_widget(canvas, chunks[1]);
 }
 
 async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> Result<()> {
     let mut last_tick = Instant::now();
-    let tick_rate = Duration::from_millis(250);
+    let mut last_refresh = Instant::now();
+    let tick_rate = app.config.tick_rate();
+    let refresh_interval = app.config.refresh_interval();
 
-    if let Ok(location) = get_location().await {
-        app.location = Some(location);
+    if app.location.is_none() {
+        app.refresh_location().await;
+        last_refresh = Instant::now();
     }
 
     loop {
@@ -141,14 +230,16 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> Result
 
         if crossterm::event::poll(timeout)? {
             if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                    KeyCode::Char('r') => {
-                        if let Ok(location) = get_location().await {
-                            app.location = Some(location);
-                        }
+                match app.config.action_for(key) {
+                    Some(Action::Quit) => return Ok(()),
+                    Some(Action::Refresh) => {
+                        app.refresh_location().await;
+                        last_refresh = Instant::now();
                     }
-                    _ => {}
+                    Some(Action::ToggleView) => app.toggle_view(),
+                    Some(Action::SpeedUp) => app.faster(),
+                    Some(Action::SlowDown) => app.slower(),
+                    None => {}
                 }
             }
         }
@@ -157,27 +248,29 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> Result
             app.update();
             last_tick = Instant::now();
         }
+
+        if last_refresh.elapsed() >= refresh_interval {
+            app.refresh_location().await;
+            last_refresh = Instant::now();
+        }
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-
-    let app = App::new();
-    let res = run_app(&mut terminal, app).await;
-
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    let config = config::load();
+
+    let mut term = terminal::init_terminal()?;
+    let (source, initial_location): (Box<dyn LocationSource>, Option<Location>) =
+        match config.location_source {
+            LocationPreference::Auto => pick_source(Accuracy::Exact).await,
+            LocationPreference::Ip => (Box::new(IpApiSource), None),
+            LocationPreference::Portal => (Box::new(PortalSource::new(Accuracy::Exact)), None),
+        };
+    let app = App::new(source, config, initial_location);
+    let res = run_app(&mut term, app).await;
+
+    terminal::restore_terminal()?;
 
     if let Err(err) = res {
         println!("{:?}", err)
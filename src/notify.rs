@@ -0,0 +1,84 @@
+use crate::location::Location;
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Great-circle distance between two locations, in kilometers.
+pub fn haversine_km(a: &Location, b: &Location) -> f64 {
+    let lat1 = a.latitude.to_radians();
+    let lat2 = b.latitude.to_radians();
+    let dlat = (b.latitude - a.latitude).to_radians();
+    let dlon = (b.longitude - a.longitude).to_radians();
+
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * h.sqrt().asin()
+}
+
+/// Whether `current` differs from `previous` enough to be worth telling the
+/// user about: a changed city/country, or a move beyond `threshold_km`.
+pub fn changed_meaningfully(previous: &Location, current: &Location, threshold_km: f64) -> bool {
+    previous.city != current.city
+        || previous.country != current.country
+        || haversine_km(previous, current) > threshold_km
+}
+
+/// Fires a desktop notification announcing the new location. Errors are
+/// swallowed since a missing notification daemon shouldn't crash the app.
+pub fn notify_location_change(location: &Location) {
+    let _ = notify_rust::Notification::new()
+        .summary("Location changed")
+        .body(&format!(
+            "Location changed: {}, {}",
+            location.city, location.country
+        ))
+        .show();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loc(latitude: f64, longitude: f64, city: &str, country: &str) -> Location {
+        Location {
+            latitude,
+            longitude,
+            city: city.to_string(),
+            country: country.to_string(),
+            altitude: None,
+            speed: None,
+            heading: None,
+            accuracy: None,
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn haversine_km_matches_known_equatorial_distance() {
+        // One degree of longitude at the equator is ~111.2 km.
+        let a = loc(0.0, 0.0, "A", "A");
+        let b = loc(0.0, 1.0, "A", "A");
+        assert!((haversine_km(&a, &b) - 111.2).abs() < 0.5);
+    }
+
+    #[test]
+    fn haversine_km_is_zero_for_identical_points() {
+        let a = loc(37.0, -122.0, "SF", "US");
+        assert!(haversine_km(&a, &a) < 1e-9);
+    }
+
+    #[test]
+    fn changed_meaningfully_detects_city_change_even_at_same_coordinates() {
+        let a = loc(10.0, 10.0, "Springfield", "US");
+        let b = loc(10.0, 10.0, "Shelbyville", "US");
+        assert!(changed_meaningfully(&a, &b, 5.0));
+    }
+
+    #[test]
+    fn changed_meaningfully_straddles_distance_threshold() {
+        let a = loc(0.0, 0.0, "A", "A");
+        // ~55.6 km away, so a 50 km threshold is exceeded...
+        let past_threshold = loc(0.0, 0.5, "A", "A");
+        assert!(changed_meaningfully(&a, &past_threshold, 50.0));
+        // ...but a 60 km threshold is not.
+        assert!(!changed_meaningfully(&a, &past_threshold, 60.0));
+    }
+}
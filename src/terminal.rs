@@ -0,0 +1,42 @@
+use anyhow::Result;
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+use std::io::{self, Stdout};
+
+/// Enables raw mode, enters the alternate screen with mouse capture, and
+/// installs a panic hook that restores the terminal before the default
+/// handler prints the panic message. Without this, a panic anywhere after
+/// `init_terminal()` and before `restore_terminal()` leaves the user's shell
+/// stuck in raw mode / the alternate screen.
+pub fn init_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let _ = restore_terminal();
+        default_hook(panic_info);
+    }));
+
+    Ok(Terminal::new(CrosstermBackend::new(stdout))?)
+}
+
+/// Disables raw mode, leaves the alternate screen, disables mouse capture,
+/// and shows the cursor again. Safe to call from both the normal exit path
+/// and the panic hook installed by `init_terminal()`.
+pub fn restore_terminal() -> Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        Show
+    )?;
+    Ok(())
+}